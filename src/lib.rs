@@ -1,7 +1,31 @@
-use image::{
-    codecs::png::PngDecoder, DynamicImage, GenericImage, GenericImageView, ImageOutputFormat, Rgba,
-};
-use std::io::{Read, Write};
+use image::{io::Reader as ImageReader, DynamicImage, RgbaImage};
+pub use image::ImageOutputFormat;
+use rayon::prelude::*;
+use std::io::{Cursor, Read, Write};
+
+const QOI_MAGIC: &[u8; 4] = b"qoif";
+
+/// diff image output format; wraps every format `image` supports, plus QOI (Quite OK Image),
+/// which `image` does not natively encode
+#[derive(Debug, Clone)]
+pub enum OutputFormat {
+    /// any format supported by `image::ImageOutputFormat`
+    Image(ImageOutputFormat),
+    /// Quite OK Image — fast to encode/decode, useful for large CI fixture sets
+    Qoi,
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Image(ImageOutputFormat::Png)
+    }
+}
+
+impl From<ImageOutputFormat> for OutputFormat {
+    fn from(format: ImageOutputFormat) -> Self {
+        OutputFormat::Image(format)
+    }
+}
 
 pub struct Options {
     /// matching threshold (0 to 1); smaller is more sensitive
@@ -18,6 +42,14 @@ pub struct Options {
     pub diff_color_alt: Option<[u8; 4]>,
     /// draw the diff over a transparent background (a mask)
     pub diff_mask: bool,
+    /// rectangles (`[x1, y1, x2, y2]`) to exclude from the diff entirely, e.g. to mask out
+    /// dynamic content such as timestamps or carousels
+    pub ignore_regions: Vec<[u32; 4]>,
+    /// color used to paint pixels falling inside an ignore region in the diff output; if unset,
+    /// the faded grayscale background is used instead (or nothing, when `diff_mask` is set)
+    pub block_out_color: Option<[u8; 4]>,
+    /// format the diff image is encoded as
+    pub output_format: OutputFormat,
 }
 
 impl Default for Options {
@@ -30,23 +62,81 @@ impl Default for Options {
             diff_color: [255, 0, 0, 255],
             diff_color_alt: None,
             diff_mask: false,
+            ignore_regions: Vec::new(),
+            block_out_color: None,
+            output_format: OutputFormat::default(),
         }
     }
 }
 
+// decode an image from any reader, auto-detecting the format instead of assuming PNG;
+// `image` doesn't natively decode QOI, so that's detected and handled separately
+fn decode_image<IMG: Read>(mut input: IMG) -> Result<DynamicImage, Box<dyn std::error::Error>> {
+    let mut buf = Vec::new();
+    input.read_to_end(&mut buf)?;
+
+    if buf.starts_with(QOI_MAGIC) {
+        let (header, pixels) = qoi::decode_to_vec(&buf)?;
+        let rgba = if header.channels == qoi::Channels::Rgb {
+            pixels.chunks(3).flat_map(|c| [c[0], c[1], c[2], 255]).collect()
+        } else {
+            pixels
+        };
+
+        let img = RgbaImage::from_raw(header.width, header.height, rgba)
+            .ok_or_else(|| <Box<dyn std::error::Error>>::from("Failed to decode QOI image."))?;
+
+        return Ok(DynamicImage::ImageRgba8(img));
+    }
+
+    Ok(ImageReader::new(Cursor::new(buf))
+        .with_guessed_format()?
+        .decode()?)
+}
+
+/// per-category tallies produced by [`pixelmatch_report`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiffReport {
+    /// pixels flagged as a real rendering difference
+    pub different: usize,
+    /// pixels that would have been flagged as different but were attributed to anti-aliasing
+    pub anti_aliased: usize,
+    /// pixels whose color delta fell within the threshold (similar but not byte-identical)
+    pub below_threshold: usize,
+    /// pixels that were byte-identical between the two images
+    pub identical: usize,
+    /// pixels excluded from comparison because they fell inside an ignore region
+    pub ignored: usize,
+    /// `100 * different / (width * height)`
+    pub diff_percent: f32,
+}
+
 pub fn pixelmatch<IMG1: Read, IMG2: Read, OUT: Write>(
     img1: IMG1,
     img2: IMG2,
-    mut output: Option<&mut OUT>,
+    output: Option<&mut OUT>,
     width: Option<u32>,
     height: Option<u32>,
     options: Option<Options>,
 ) -> Result<usize, Box<dyn std::error::Error>> {
-    let img1 = DynamicImage::from_decoder(PngDecoder::new(img1)?)?;
-    let img2 = DynamicImage::from_decoder(PngDecoder::new(img2)?)?;
+    Ok(pixelmatch_report(img1, img2, output, width, height, options)?.different)
+}
+
+/// like [`pixelmatch`], but returns a [`DiffReport`] with per-category tallies instead of just
+/// the mismatch count
+pub fn pixelmatch_report<IMG1: Read, IMG2: Read, OUT: Write>(
+    img1: IMG1,
+    img2: IMG2,
+    mut output: Option<&mut OUT>,
+    width: Option<u32>,
+    height: Option<u32>,
+    options: Option<Options>,
+) -> Result<DiffReport, Box<dyn std::error::Error>> {
+    let img1 = decode_image(img1)?.to_rgba8();
+    let img2 = decode_image(img2)?.to_rgba8();
 
     let img1_dimensions = img1.dimensions();
-    if img1.dimensions() != img2.dimensions() {
+    if img1_dimensions != img2.dimensions() {
         return Err(<Box<dyn std::error::Error>>::from(
             "Image sizes do not match.",
         ));
@@ -61,104 +151,419 @@ pub fn pixelmatch<IMG1: Read, IMG2: Read, OUT: Write>(
     }
 
     let options = options.unwrap_or_default();
-    let mut img_out = match output {
-        Some(..) => Some(DynamicImage::new_rgba8(
-            img1_dimensions.0,
-            img1_dimensions.1,
-        )),
-        None => None,
+    let (width, height) = img1_dimensions;
+    let img1_buf: &[u8] = img1.as_raw();
+    let img2_buf: &[u8] = img2.as_raw();
+
+    // maximum acceptable square distance between two colors;
+    // 35215 is the maximum possible value for the YIQ difference metric
+    let max_delta = 35215_f32 * options.threshold * options.threshold;
+    let row_bytes = (width * 4) as usize;
+
+    let mut out_buf = output
+        .is_some()
+        .then(|| vec![0_u8; row_bytes * height as usize]);
+
+    // each row only ever reads neighboring pixels from img1_buf/img2_buf and writes its own
+    // slice of out_buf, so rows can be diffed independently across threads
+    let report = if let Some(out_buf) = out_buf.as_mut() {
+        out_buf
+            .par_chunks_mut(row_bytes)
+            .enumerate()
+            .map(|(y, row_out)| {
+                diff_row(
+                    y as u32,
+                    width,
+                    height,
+                    img1_buf,
+                    img2_buf,
+                    &options,
+                    max_delta,
+                    Some(row_out),
+                )
+            })
+            .reduce(DiffReport::default, add_reports)
+    } else {
+        (0..height)
+            .into_par_iter()
+            .map(|y| {
+                diff_row(
+                    y, width, height, img1_buf, img2_buf, &options, max_delta, None,
+                )
+            })
+            .reduce(DiffReport::default, add_reports)
     };
 
-    // check if images are identical
-    let mut identical = true;
-    for (pixel1, pixel2) in img1.pixels().zip(img2.pixels()) {
-        if pixel1 != pixel2 {
-            identical = false;
-            break;
+    if let (Some(output), Some(out_buf)) = (&mut output, out_buf) {
+        write_diff_image(*output, out_buf, width, height, &options.output_format)?;
+    }
+
+    let mut report = report;
+    report.diff_percent = 100.0 * report.different as f32 / (width * height) as f32;
+
+    Ok(report)
+}
+
+/// per-frame report produced by [`pixelmatch_sequence`]; extends [`DiffReport`] with the count
+/// of pixels that would have been flagged as different but were attributed to sub-frame timing
+/// jitter instead of a real rendering change
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SequenceFrameReport {
+    pub diff: DiffReport,
+    pub temporally_suppressed: usize,
+}
+
+/// result of a [`pixelmatch_sequence`] call
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SequenceReport {
+    /// one report per frame, in the order the frames were given
+    pub frames: Vec<SequenceFrameReport>,
+    /// sum of `frames[..].diff.different`
+    pub total_different: usize,
+}
+
+/// compare two equal-length sequences of frames (e.g. the frames of an animation export),
+/// suppressing per-pixel differences that are caused by sub-frame timing jitter rather than a
+/// real rendering change.
+///
+/// for each differing pixel, a window of `temporal_window` frames on either side of the current
+/// frame is searched, in the *other* sequence, for a matching color; if one is found, the pixel
+/// is treated as unchanged instead of different. `temporal_window == 0` disables this and is
+/// equivalent to calling [`pixelmatch_report`] once per frame.
+pub fn pixelmatch_sequence<OUT: Write>(
+    frames1: &[Vec<u8>],
+    frames2: &[Vec<u8>],
+    mut outputs: Option<&mut Vec<OUT>>,
+    temporal_window: usize,
+    options: Option<Options>,
+) -> Result<SequenceReport, Box<dyn std::error::Error>> {
+    if frames1.len() != frames2.len() {
+        return Err(<Box<dyn std::error::Error>>::from(
+            "Sequences must have the same number of frames.",
+        ));
+    }
+
+    if let Some(outputs) = &outputs {
+        if outputs.len() != frames1.len() {
+            return Err(<Box<dyn std::error::Error>>::from(
+                "Outputs must have one entry per frame.",
+            ));
         }
     }
 
-    // fast path if identical
-    if identical {
-        if let (Some(output), Some(img_out)) = (&mut output, &mut img_out) {
-            if !options.diff_mask {
-                for pixel in img1.pixels() {
-                    draw_gray_pixel(&pixel, options.alpha, img_out)?;
+    let options = options.unwrap_or_default();
+
+    let seq1 = frames1
+        .iter()
+        .map(|frame| Ok(decode_image(frame.as_slice())?.to_rgba8()))
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+    let seq2 = frames2
+        .iter()
+        .map(|frame| Ok(decode_image(frame.as_slice())?.to_rgba8()))
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+
+    let dimensions = seq1.first().map(RgbaImage::dimensions);
+    if seq1
+        .iter()
+        .chain(seq2.iter())
+        .any(|frame| Some(frame.dimensions()) != dimensions)
+    {
+        return Err(<Box<dyn std::error::Error>>::from(
+            "All frames in a sequence must share the same dimensions.",
+        ));
+    }
+
+    let mut frames = Vec::with_capacity(seq1.len());
+    for t in 0..seq1.len() {
+        let output = outputs.as_mut().map(|outputs| &mut outputs[t]);
+        frames.push(diff_frame(&seq1, &seq2, t, temporal_window, &options, output)?);
+    }
+
+    let total_different = frames.iter().map(|frame| frame.diff.different).sum();
+
+    Ok(SequenceReport {
+        frames,
+        total_different,
+    })
+}
+
+// diff a single frame of a sequence against its counterpart, consulting a temporal window of
+// neighboring frames in the other sequence before flagging a pixel as a real difference
+fn diff_frame<OUT: Write>(
+    seq1: &[RgbaImage],
+    seq2: &[RgbaImage],
+    t: usize,
+    temporal_window: usize,
+    options: &Options,
+    mut output: Option<&mut OUT>,
+) -> Result<SequenceFrameReport, Box<dyn std::error::Error>> {
+    let (width, height) = seq1[t].dimensions();
+    let img1_buf: &[u8] = seq1[t].as_raw();
+    let img2_buf: &[u8] = seq2[t].as_raw();
+
+    let max_delta = 35215_f32 * options.threshold * options.threshold;
+    let mut out_buf = output
+        .is_some()
+        .then(|| vec![0_u8; (width * height * 4) as usize]);
+    let mut report = SequenceFrameReport::default();
+
+    for y in 0..height {
+        for x in 0..width {
+            let offset = ((y * width + x) * 4) as usize;
+
+            if in_ignore_regions(x, y, &options.ignore_regions) {
+                report.diff.ignored += 1;
+
+                if let Some(out_buf) = out_buf.as_mut() {
+                    if let Some(block_out_color) = options.block_out_color {
+                        out_buf[offset..offset + 4].copy_from_slice(&block_out_color);
+                    } else if !options.diff_mask {
+                        draw_gray_pixel(
+                            pixel_at(img1_buf, width, x, y),
+                            options.alpha,
+                            &mut out_buf[offset..offset + 4],
+                        );
+                    }
+                }
+
+                continue;
+            }
+
+            let pixel1 = pixel_at(img1_buf, width, x, y);
+            let pixel2 = pixel_at(img2_buf, width, x, y);
+
+            if pixel1 == pixel2 {
+                report.diff.identical += 1;
+
+                if let Some(out_buf) = out_buf.as_mut() {
+                    if !options.diff_mask {
+                        draw_gray_pixel(pixel1, options.alpha, &mut out_buf[offset..offset + 4]);
+                    }
+                }
+
+                continue;
+            }
+
+            let delta = color_delta(pixel1, pixel2, false);
+            if delta.abs() <= max_delta {
+                report.diff.below_threshold += 1;
+
+                if let Some(out_buf) = out_buf.as_mut() {
+                    if !options.diff_mask {
+                        draw_gray_pixel(pixel1, options.alpha, &mut out_buf[offset..offset + 4]);
+                    }
                 }
+
+                continue;
             }
 
-            img_out.write_to(*output, ImageOutputFormat::Png)?;
+            if temporal_window > 0
+                && (window_has_color(seq2, x, y, t, temporal_window, pixel1)
+                    || window_has_color(seq1, x, y, t, temporal_window, pixel2))
+            {
+                // sub-frame timing jitter, not a real rendering difference
+                report.temporally_suppressed += 1;
+
+                if let Some(out_buf) = out_buf.as_mut() {
+                    if !options.diff_mask {
+                        draw_gray_pixel(pixel1, options.alpha, &mut out_buf[offset..offset + 4]);
+                    }
+                }
+            } else if !options.include_aa
+                && (antialiased(img1_buf, x, y, width, height, img2_buf)
+                    || antialiased(img2_buf, x, y, width, height, img1_buf))
+            {
+                report.diff.anti_aliased += 1;
+
+                if let Some(out_buf) = out_buf.as_mut() {
+                    if !options.diff_mask {
+                        out_buf[offset..offset + 4].copy_from_slice(&options.aa_color);
+                    }
+                }
+            } else {
+                if let Some(out_buf) = out_buf.as_mut() {
+                    let color = if delta < 0.0 {
+                        options.diff_color_alt.unwrap_or(options.diff_color)
+                    } else {
+                        options.diff_color
+                    };
+                    out_buf[offset..offset + 4].copy_from_slice(&color);
+                }
+                report.diff.different += 1;
+            }
         }
+    }
 
-        return Ok(0);
+    report.diff.diff_percent = 100.0 * report.diff.different as f32 / (width * height) as f32;
+
+    if let (Some(output), Some(out_buf)) = (&mut output, out_buf) {
+        write_diff_image(*output, out_buf, width, height, &options.output_format)?;
     }
 
-    // maximum acceptable square distance between two colors;
-    // 35215 is the maximum possible value for the YIQ difference metric
-    let max_delta = 35215_f32 * options.threshold * options.threshold;
-    let mut diff: usize = 0;
+    Ok(report)
+}
+
+// check whether `color` appears at (x, y) in any frame within `window` frames of `t` (inclusive)
+// in the given sequence
+fn window_has_color(
+    seq: &[RgbaImage],
+    x: u32,
+    y: u32,
+    t: usize,
+    window: usize,
+    color: &[u8],
+) -> bool {
+    let start = t.saturating_sub(window);
+    let end = (t + window).min(seq.len() - 1);
+
+    (start..=end).any(|i| pixel_at(seq[i].as_raw(), seq[i].width(), x, y) == color)
+}
+
+// diff a single row; only reads neighboring pixels from img1_buf/img2_buf and, if given, writes
+// its own output row, so this can run independently of every other row
+#[allow(clippy::too_many_arguments)]
+fn diff_row(
+    y: u32,
+    width: u32,
+    height: u32,
+    img1_buf: &[u8],
+    img2_buf: &[u8],
+    options: &Options,
+    max_delta: f32,
+    mut row_out: Option<&mut [u8]>,
+) -> DiffReport {
+    let mut report = DiffReport::default();
+
+    for x in 0..width {
+        let offset = (x * 4) as usize;
+
+        if in_ignore_regions(x, y, &options.ignore_regions) {
+            report.ignored += 1;
+
+            if let Some(row_out) = row_out.as_deref_mut() {
+                if let Some(block_out_color) = options.block_out_color {
+                    row_out[offset..offset + 4].copy_from_slice(&block_out_color);
+                } else if !options.diff_mask {
+                    draw_gray_pixel(
+                        pixel_at(img1_buf, width, x, y),
+                        options.alpha,
+                        &mut row_out[offset..offset + 4],
+                    );
+                }
+            }
+
+            continue;
+        }
+
+        let pixel1 = pixel_at(img1_buf, width, x, y);
+        let pixel2 = pixel_at(img2_buf, width, x, y);
 
-    for (pixel1, pixel2) in img1.pixels().zip(img2.pixels()) {
-        let delta = color_delta(&pixel1.2, &pixel2.2, false);
+        if pixel1 == pixel2 {
+            report.identical += 1;
+
+            if let Some(row_out) = row_out.as_deref_mut() {
+                if !options.diff_mask {
+                    draw_gray_pixel(pixel1, options.alpha, &mut row_out[offset..offset + 4]);
+                }
+            }
+
+            continue;
+        }
+
+        let delta = color_delta(pixel1, pixel2, false);
         if delta.abs() > max_delta {
             // check it's a real rendering difference or just anti-aliasing
             if !options.include_aa
-                && (antialiased(
-                    &img1,
-                    pixel1.0,
-                    pixel1.1,
-                    img1_dimensions.0,
-                    img1_dimensions.1,
-                    &img2,
-                ) || antialiased(
-                    &img2,
-                    pixel1.0,
-                    pixel1.1,
-                    img1_dimensions.0,
-                    img1_dimensions.1,
-                    &img1,
-                ))
+                && (antialiased(img1_buf, x, y, width, height, img2_buf)
+                    || antialiased(img2_buf, x, y, width, height, img1_buf))
             {
                 // one of the pixels is anti-aliasing; draw as yellow and do not count as difference
                 // note that we do not include such pixels in a mask
-                if let (Some(img_out), false) = (&mut img_out, options.diff_mask) {
-                    img_out.put_pixel(pixel1.0, pixel1.1, Rgba(options.aa_color));
+                report.anti_aliased += 1;
+
+                if let Some(row_out) = row_out.as_deref_mut() {
+                    if !options.diff_mask {
+                        row_out[offset..offset + 4].copy_from_slice(&options.aa_color);
+                    }
                 }
             } else {
                 // found substantial difference not caused by anti-aliasing; draw it as such
-                if let Some(img_out) = &mut img_out {
+                if let Some(row_out) = row_out.as_deref_mut() {
                     let color = if delta < 0.0 {
                         options.diff_color_alt.unwrap_or(options.diff_color)
                     } else {
                         options.diff_color
                     };
-                    img_out.put_pixel(pixel1.0, pixel1.1, Rgba(color));
+                    row_out[offset..offset + 4].copy_from_slice(&color);
                 }
-                diff += 1;
+                report.different += 1;
             }
-        } else if let (Some(img_out), false) = (&mut img_out, options.diff_mask) {
+        } else {
             // pixels are similar; draw background as grayscale image blended with white
-            draw_gray_pixel(&pixel1, options.alpha, img_out)?;
+            report.below_threshold += 1;
+
+            if let Some(row_out) = row_out.as_deref_mut() {
+                if !options.diff_mask {
+                    draw_gray_pixel(pixel1, options.alpha, &mut row_out[offset..offset + 4]);
+                }
+            }
         }
     }
 
-    if let (Some(output), Some(img_out)) = (&mut output, &mut img_out) {
-        img_out.write_to(*output, ImageOutputFormat::Png)?;
+    report
+}
+
+// encode a flat RGBA8 diff buffer and write it out in the configured format
+fn write_diff_image<OUT: Write>(
+    output: &mut OUT,
+    out_buf: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: &OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Qoi => {
+            output.write_all(&qoi::encode_to_vec(&out_buf, width, height)?)?;
+        }
+        OutputFormat::Image(format) => {
+            let img_out = RgbaImage::from_raw(width, height, out_buf)
+                .ok_or_else(|| <Box<dyn std::error::Error>>::from("Failed to assemble diff image."))?;
+            DynamicImage::ImageRgba8(img_out).write_to(output, format.clone())?;
+        }
     }
 
-    Ok(diff)
+    Ok(())
+}
+
+fn add_reports(a: DiffReport, b: DiffReport) -> DiffReport {
+    DiffReport {
+        different: a.different + b.different,
+        anti_aliased: a.anti_aliased + b.anti_aliased,
+        below_threshold: a.below_threshold + b.below_threshold,
+        identical: a.identical + b.identical,
+        ignored: a.ignored + b.ignored,
+        diff_percent: 0.0,
+    }
+}
+
+// read a pixel at (x, y) out of a flat RGBA8 buffer without going through `DynamicImage`'s
+// per-access bounds checks and enum dispatch
+#[inline]
+fn pixel_at(buf: &[u8], width: u32, x: u32, y: u32) -> &[u8] {
+    let offset = ((y * width + x) * 4) as usize;
+    &buf[offset..offset + 4]
+}
+
+// check if a pixel falls within any of the configured ignore/block-out regions
+fn in_ignore_regions(x: u32, y: u32, regions: &[[u32; 4]]) -> bool {
+    regions
+        .iter()
+        .any(|&[x1, y1, x2, y2]| x >= x1 && x < x2 && y >= y1 && y < y2)
 }
 
 // check if a pixel is likely a part of anti-aliasing;
 // based on "Anti-aliased Pixel and Intensity Slope Detector" paper by V. Vysniauskas, 2009
-fn antialiased(
-    img1: &DynamicImage,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
-    img2: &DynamicImage,
-) -> bool {
+fn antialiased(img1: &[u8], x: u32, y: u32, width: u32, height: u32, img2: &[u8]) -> bool {
     let mut zeroes: u8 = 0;
     let mut min = 0.0;
     let mut max = 0.0;
@@ -167,7 +572,7 @@ fn antialiased(
     let mut max_x = 0;
     let mut max_y = 0;
 
-    let center_rgba = img1.get_pixel(x, y);
+    let center_rgba = pixel_at(img1, width, x, y);
 
     for relative_x in -1_i32..=1 {
         for relative_y in -1_i32..=1 {
@@ -184,8 +589,8 @@ fn antialiased(
                 .saturating_add(relative_y)
                 .max(0)
                 .min(height as i32 - 1) as u32;
-            let rgba = img1.get_pixel(adjacent_x, adjacent_y);
-            let delta = color_delta(&center_rgba, &rgba, true);
+            let rgba = pixel_at(img1, width, adjacent_x, adjacent_y);
+            let delta = color_delta(center_rgba, rgba, true);
 
             // count the number of equal, darker and brighter adjacent pixels
             if delta == 0.0 {
@@ -230,10 +635,10 @@ fn antialiased(
 }
 
 // check if a pixel has 3+ adjacent pixels of the same color.
-fn has_many_siblings(img: &DynamicImage, x: u32, y: u32, width: u32, height: u32) -> bool {
+fn has_many_siblings(img: &[u8], x: u32, y: u32, width: u32, height: u32) -> bool {
     let mut zeroes: u8 = 0;
 
-    let center_rgba = img.get_pixel(x, y);
+    let center_rgba = pixel_at(img, width, x, y);
 
     for relative_x in -1_i32..=1 {
         for relative_y in -1_i32..=1 {
@@ -249,7 +654,7 @@ fn has_many_siblings(img: &DynamicImage, x: u32, y: u32, width: u32, height: u32
                 .saturating_add(relative_y)
                 .max(0)
                 .min(height as i32 - 1) as u32;
-            let rgba = img.get_pixel(adjacent_x, adjacent_y);
+            let rgba = pixel_at(img, width, adjacent_x, adjacent_y);
 
             if center_rgba == rgba {
                 zeroes += 1;
@@ -266,7 +671,7 @@ fn has_many_siblings(img: &DynamicImage, x: u32, y: u32, width: u32, height: u32
 
 // calculate color difference according to the paper "Measuring perceived color difference
 // using YIQ NTSC transmission color space in mobile applications" by Y. Kotsarenko and F. Ramos
-fn color_delta(rgba1: &Rgba<u8>, rgba2: &Rgba<u8>, y_only: bool) -> f32 {
+fn color_delta(rgba1: &[u8], rgba2: &[u8], y_only: bool) -> f32 {
     let mut r1 = rgba1[0] as f32;
     let mut g1 = rgba1[1] as f32;
     let mut b1 = rgba1[2] as f32;
@@ -317,25 +722,13 @@ fn color_delta(rgba1: &Rgba<u8>, rgba2: &Rgba<u8>, y_only: bool) -> f32 {
     }
 }
 
-fn draw_gray_pixel(
-    (x, y, rgba): &(u32, u32, Rgba<u8>),
-    alpha: f32,
-    output: &mut DynamicImage,
-) -> Result<(), Box<dyn std::error::Error>> {
-    if !output.in_bounds(*x, *y) {
-        return Err(<Box<dyn std::error::Error>>::from(
-            "Pixel is not in bounds of output.",
-        ));
-    }
-
+// blend a pixel down to grayscale and write it directly into its output slot
+fn draw_gray_pixel(rgba: &[u8], alpha: f32, out: &mut [u8]) {
     let val = blend(
         rgb2y(rgba[0], rgba[1], rgba[2]),
         (alpha * rgba[3] as f32) / 255.0,
     ) as u8;
-    let gray_rgba = Rgba([val, val, val, val]);
-    output.put_pixel(*x, *y, gray_rgba);
-
-    Ok(())
+    out.copy_from_slice(&[val, val, val, val]);
 }
 
 // blend semi-transparent color with white
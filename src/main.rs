@@ -1,6 +1,6 @@
-use image::{codecs::png::PngDecoder, ImageDecoder};
-use pixelmatch::{pixelmatch, Options};
-use std::{fs, path::PathBuf, process, time};
+use image::{io::Reader as ImageReader, ImageOutputFormat};
+use pixelmatch::{pixelmatch, Options, OutputFormat};
+use std::{fs, io::Cursor, path::PathBuf, process, time};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -25,6 +25,40 @@ struct Opt {
     /// Include antialiasing
     #[structopt(short, long)]
     include_aa: Option<bool>,
+
+    /// Format to encode the diff image as (e.g. png, jpeg, bmp, gif, tga, ico, qoi)
+    #[structopt(short, long)]
+    format: Option<String>,
+}
+
+// `image`'s format guesser doesn't know QOI, so that's detected by its magic bytes separately
+fn read_dimensions(bytes: &[u8]) -> Result<(u32, u32), Box<dyn std::error::Error>> {
+    if bytes.starts_with(b"qoif") {
+        let header = qoi::decode_header(bytes)?;
+        return Ok((header.width, header.height));
+    }
+
+    Ok(ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()?
+        .into_dimensions()?)
+}
+
+fn parse_output_format(format: &str) -> Result<OutputFormat, Box<dyn std::error::Error>> {
+    Ok(match format.to_lowercase().as_str() {
+        "png" => ImageOutputFormat::Png.into(),
+        "jpeg" | "jpg" => ImageOutputFormat::Jpeg(100).into(),
+        "bmp" => ImageOutputFormat::Bmp.into(),
+        "gif" => ImageOutputFormat::Gif.into(),
+        "ico" => ImageOutputFormat::Ico.into(),
+        "tga" => ImageOutputFormat::Tga.into(),
+        "qoi" => OutputFormat::Qoi,
+        other => {
+            return Err(<Box<dyn std::error::Error>>::from(format!(
+                "Unsupported output format: {}",
+                other
+            )))
+        }
+    })
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -37,12 +71,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Some(include_aa) = opt.include_aa {
         options.include_aa = include_aa;
     }
+    if let Some(format) = &opt.format {
+        options.output_format = parse_output_format(format)?;
+    }
 
     let img1 = fs::read(opt.img1_path)?;
     let img2 = fs::read(opt.img2_path)?;
 
-    let (width1, height1) = PngDecoder::new(img1.as_slice())?.dimensions();
-    let (width2, height2) = PngDecoder::new(img2.as_slice())?.dimensions();
+    let (width1, height1) = read_dimensions(&img1)?;
+    let (width2, height2) = read_dimensions(&img2)?;
     if width1 != width2 || height1 != height2 {
         println!(
             "Image dimensions do not match: {}x{} vs {}x{}",
@@ -1,7 +1,26 @@
 use paste::paste;
-use pixelmatch::{pixelmatch, Options};
+use pixelmatch::{pixelmatch, pixelmatch_report, pixelmatch_sequence, Options, OutputFormat};
 use std::{env, fs, path::PathBuf};
 
+const BLACK: [u8; 4] = [0, 0, 0, 255];
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+// encode a small in-memory PNG for tests that don't need a fixture file, just a known pixel grid
+fn encode_png(width: u32, height: u32, pixel: impl Fn(u32, u32) -> [u8; 4]) -> Vec<u8> {
+    let mut img = image::RgbaImage::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            img.put_pixel(x, y, image::Rgba(pixel(x, y)));
+        }
+    }
+
+    let mut out = Vec::new();
+    image::DynamicImage::ImageRgba8(img)
+        .write_to(&mut out, image::ImageOutputFormat::Png)
+        .unwrap();
+    out
+}
+
 macro_rules! diff_test {
   ($img1_path: ident, $img2_path: ident, $diff_path: ident, $options: expr, $options_name: ident, $expected_mismatch: literal) => {
     paste! {
@@ -161,3 +180,226 @@ fn throws_error_if_image_sizes_do_not_match() {
         Err(String::from("Image sizes do not match"))
     );
 }
+
+#[test]
+fn ignore_regions_exclude_pixels_from_the_diff() -> Result<(), Box<dyn std::error::Error>> {
+    // both corners differ, but only the top-left one falls outside the ignore region
+    let img1 = encode_png(4, 4, |_, _| BLACK);
+    let img2 = encode_png(4, 4, |x, y| {
+        if (x, y) == (0, 0) || (x, y) == (3, 3) {
+            WHITE
+        } else {
+            BLACK
+        }
+    });
+
+    let options = Options {
+        threshold: 0.1,
+        ignore_regions: vec![[3, 3, 4, 4]],
+        ..Default::default()
+    };
+
+    let mismatches = pixelmatch(
+        img1.as_slice(),
+        img2.as_slice(),
+        Option::<&mut Vec<u8>>::None,
+        None,
+        None,
+        Some(options),
+    )?;
+
+    assert_eq!(mismatches, 1);
+
+    Ok(())
+}
+
+#[test]
+fn block_out_color_paints_ignored_regions() -> Result<(), Box<dyn std::error::Error>> {
+    let img1 = encode_png(2, 2, |_, _| BLACK);
+    let img2 = encode_png(2, 2, |_, _| BLACK);
+
+    let options = Options {
+        ignore_regions: vec![[0, 0, 2, 2]],
+        block_out_color: Some([1, 2, 3, 4]),
+        ..Default::default()
+    };
+
+    let mut diff = Vec::new();
+    pixelmatch(
+        img1.as_slice(),
+        img2.as_slice(),
+        Some(&mut diff),
+        None,
+        None,
+        Some(options),
+    )?;
+
+    let decoded = image::load_from_memory(&diff)?.to_rgba8();
+    for pixel in decoded.pixels() {
+        assert_eq!(pixel.0, [1, 2, 3, 4]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn pixelmatch_report_tallies_each_category() -> Result<(), Box<dyn std::error::Error>> {
+    // a single different pixel among three identical ones
+    let img1 = encode_png(2, 2, |_, _| BLACK);
+    let img2 = encode_png(2, 2, |x, y| if (x, y) == (0, 0) { WHITE } else { BLACK });
+
+    let report = pixelmatch_report(
+        img1.as_slice(),
+        img2.as_slice(),
+        Option::<&mut Vec<u8>>::None,
+        None,
+        None,
+        Some(Options {
+            threshold: 0.1,
+            ..Default::default()
+        }),
+    )?;
+
+    assert_eq!(report.different, 1);
+    assert_eq!(report.identical, 3);
+    assert_eq!(report.below_threshold, 0);
+    assert_eq!(report.ignored, 0);
+    assert_eq!(report.diff_percent, 25.0);
+
+    Ok(())
+}
+
+#[test]
+fn qoi_input_is_auto_detected() -> Result<(), Box<dyn std::error::Error>> {
+    let pixels1: Vec<u8> = (0..4).flat_map(|_| BLACK).collect();
+    let mut pixels2 = pixels1.clone();
+    pixels2[0..4].copy_from_slice(&WHITE);
+
+    let qoi1 = qoi::encode_to_vec(&pixels1, 2, 2)?;
+    let qoi2 = qoi::encode_to_vec(&pixels2, 2, 2)?;
+
+    let mismatches = pixelmatch(
+        qoi1.as_slice(),
+        qoi2.as_slice(),
+        Option::<&mut Vec<u8>>::None,
+        None,
+        None,
+        Some(Options {
+            threshold: 0.1,
+            ..Default::default()
+        }),
+    )?;
+
+    assert_eq!(mismatches, 1);
+
+    Ok(())
+}
+
+#[test]
+fn qoi_output_format_round_trips_through_the_qoi_decoder() -> Result<(), Box<dyn std::error::Error>>
+{
+    let img1 = encode_png(2, 2, |_, _| BLACK);
+    let img2 = encode_png(2, 2, |x, y| if (x, y) == (0, 0) { WHITE } else { BLACK });
+
+    let mut diff = Vec::new();
+    pixelmatch(
+        img1.as_slice(),
+        img2.as_slice(),
+        Some(&mut diff),
+        None,
+        None,
+        Some(Options {
+            threshold: 0.1,
+            output_format: OutputFormat::Qoi,
+            ..Default::default()
+        }),
+    )?;
+
+    let (header, _) = qoi::decode_to_vec(&diff)?;
+    assert_eq!((header.width, header.height), (2, 2));
+
+    Ok(())
+}
+
+// encode a single 2x2 frame as QOI, for the pixelmatch_sequence tests below
+fn qoi_frame(pixels: [[u8; 4]; 4]) -> Vec<u8> {
+    let raw: Vec<u8> = pixels.iter().flatten().copied().collect();
+    qoi::encode_to_vec(&raw, 2, 2).unwrap()
+}
+
+#[test]
+fn temporal_window_suppresses_sub_frame_flicker() -> Result<(), Box<dyn std::error::Error>> {
+    // pixel (0, 0) flickers white in seq2's middle frame, but that same white also shows up in
+    // seq1 one frame later, so a window of 1 should treat it as jitter, not a real difference
+    let frames1 = vec![
+        qoi_frame([BLACK, BLACK, BLACK, BLACK]),
+        qoi_frame([BLACK, BLACK, BLACK, BLACK]),
+        qoi_frame([WHITE, BLACK, BLACK, BLACK]),
+    ];
+    let frames2 = vec![
+        qoi_frame([BLACK, BLACK, BLACK, BLACK]),
+        qoi_frame([WHITE, BLACK, BLACK, BLACK]),
+        qoi_frame([BLACK, BLACK, BLACK, BLACK]),
+    ];
+
+    let suppressed = pixelmatch_sequence(
+        &frames1,
+        &frames2,
+        Option::<&mut Vec<Vec<u8>>>::None,
+        1,
+        Some(Options {
+            threshold: 0.1,
+            ..Default::default()
+        }),
+    )?;
+    assert_eq!(suppressed.frames[1].diff.different, 0);
+    assert_eq!(suppressed.frames[1].temporally_suppressed, 1);
+    assert_eq!(suppressed.total_different, 0);
+
+    // with no temporal window, the flicker is just counted as a difference on the frame where
+    // the two sequences disagree, the same as diffing each frame with pixelmatch_report
+    let unsuppressed = pixelmatch_sequence(
+        &frames1,
+        &frames2,
+        Option::<&mut Vec<Vec<u8>>>::None,
+        0,
+        Some(Options {
+            threshold: 0.1,
+            ..Default::default()
+        }),
+    )?;
+    assert_eq!(unsuppressed.frames[1].diff.different, 1);
+    assert_eq!(unsuppressed.frames[1].temporally_suppressed, 0);
+
+    for t in 0..frames1.len() {
+        let per_frame_report = pixelmatch_report(
+            frames1[t].as_slice(),
+            frames2[t].as_slice(),
+            Option::<&mut Vec<u8>>::None,
+            None,
+            None,
+            Some(Options {
+                threshold: 0.1,
+                ..Default::default()
+            }),
+        )?;
+        assert_eq!(unsuppressed.frames[t].diff, per_frame_report);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn pixelmatch_sequence_rejects_mismatched_frame_counts() {
+    let frames1 = vec![qoi_frame([BLACK, BLACK, BLACK, BLACK])];
+    let frames2 = vec![
+        qoi_frame([BLACK, BLACK, BLACK, BLACK]),
+        qoi_frame([BLACK, BLACK, BLACK, BLACK]),
+    ];
+
+    assert_eq!(
+        pixelmatch_sequence(&frames1, &frames2, Option::<&mut Vec<Vec<u8>>>::None, 0, None)
+            .map_err(|err| err.to_string()),
+        Err(String::from("Sequences must have the same number of frames."))
+    );
+}